@@ -0,0 +1,39 @@
+use assert_cmd::Command;
+use std::{fs, path::Path};
+
+use xtask::checker::{CheckerConfig, Verdict};
+
+/// Run every `NNN.in`/`NNN.out` pair in `dir` against `bin_name`, comparing
+/// with the task's [`CheckerConfig`] (defaults to byte-exact when no
+/// `judge.toml` is present).
+pub fn run_all_cases(dir: &str, bin_name: &str) {
+    let dir = Path::new(dir);
+    if !dir.exists() {
+        return;
+    }
+    let checker = CheckerConfig::load(dir).expect("failed to load judge.toml");
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|s| s.to_str()) == Some("in") {
+            let stem = path.file_stem().unwrap().to_string_lossy();
+            let expected_path = dir.join(format!("{stem}.out"));
+            let input = fs::read_to_string(&path).unwrap();
+            let expected = fs::read_to_string(&expected_path).unwrap();
+
+            let mut cmd = Command::cargo_bin(bin_name).unwrap();
+            let output = cmd.write_stdin(input).assert().success();
+            let actual = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+            match checker
+                .check(&path, &expected_path, &expected, &actual)
+                .expect("failed to run checker")
+            {
+                Verdict::Accepted => {}
+                Verdict::WrongAnswer(reason) => {
+                    panic!("case {stem} failed: {reason}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// Outcome of comparing an actual output against the expected one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer(String),
+}
+
+/// Per-task checker configuration, loaded from `tests/<task>/judge.toml`.
+/// Defaults to [`CheckerConfig::Exact`] when no such file exists, which
+/// preserves the historical byte-exact `stdout(expected)` behavior.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckerConfig {
+    /// Byte-for-byte comparison (the original behavior).
+    Exact,
+    /// Whitespace-tokenized comparison, ignoring trailing blank lines.
+    Tokens,
+    /// Whitespace-tokenized comparison where numeric tokens are accepted
+    /// within `abs` or `rel * |expected|` of each other.
+    Float { abs: f64, rel: f64 },
+    /// Delegate to an external binary invoked as `checker <input> <expected> <actual>`.
+    Checker { path: PathBuf },
+}
+
+impl CheckerConfig {
+    /// Load the checker config for a task's sample directory, e.g. `tests/d`.
+    pub fn load(task_dir: &Path) -> Result<Self> {
+        let config_path = task_dir.join("judge.toml");
+        if !config_path.exists() {
+            return Ok(CheckerConfig::Exact);
+        }
+
+        let contents = fs::read_to_string(&config_path)
+            .with_context(|| format!("failed to read {}", config_path.display()))?;
+        Self::parse(&contents, task_dir)
+    }
+
+    fn parse(contents: &str, task_dir: &Path) -> Result<Self> {
+        let mut fields: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = strip_inline_comment(value.trim()).trim_matches('"').to_string();
+            fields.insert(key.trim().to_string(), value);
+        }
+
+        let mode = fields
+            .get("mode")
+            .map(String::as_str)
+            .unwrap_or("exact");
+
+        match mode {
+            "exact" => Ok(CheckerConfig::Exact),
+            "tokens" => Ok(CheckerConfig::Tokens),
+            "float" => {
+                let abs = fields
+                    .get("abs")
+                    .map(|v| v.parse::<f64>())
+                    .transpose()
+                    .context("invalid 'abs' in judge.toml")?
+                    .unwrap_or(1e-6);
+                let rel = fields
+                    .get("rel")
+                    .map(|v| v.parse::<f64>())
+                    .transpose()
+                    .context("invalid 'rel' in judge.toml")?
+                    .unwrap_or(1e-6);
+                Ok(CheckerConfig::Float { abs, rel })
+            }
+            "checker" => {
+                let path = fields
+                    .get("path")
+                    .context("judge.toml mode = \"checker\" requires a 'path'")?;
+                Ok(CheckerConfig::Checker {
+                    path: task_dir.join(path),
+                })
+            }
+            other => anyhow::bail!("unknown judge.toml mode '{other}'"),
+        }
+    }
+
+    /// Compare `actual` against `expected` for one sample case.
+    pub fn check(&self, input_path: &Path, expected_path: &Path, expected: &str, actual: &str) -> Result<Verdict> {
+        match self {
+            CheckerConfig::Exact => Ok(compare_exact(expected, actual)),
+            CheckerConfig::Tokens => Ok(compare_tokens(expected, actual, None)),
+            CheckerConfig::Float { abs, rel } => Ok(compare_tokens(expected, actual, Some((*abs, *rel)))),
+            CheckerConfig::Checker { path } => run_external_checker(path, input_path, expected_path, actual),
+        }
+    }
+}
+
+/// Strip a trailing `# comment` from a judge.toml value, ignoring any `#`
+/// that appears inside a `"..."` quoted string so quoted values can contain
+/// one.
+fn strip_inline_comment(value: &str) -> &str {
+    let mut in_quotes = false;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '#' if !in_quotes => return value[..index].trim_end(),
+            _ => {}
+        }
+    }
+    value
+}
+
+fn compare_exact(expected: &str, actual: &str) -> Verdict {
+    if expected == actual {
+        Verdict::Accepted
+    } else {
+        Verdict::WrongAnswer("output did not match expected exactly".to_string())
+    }
+}
+
+fn compare_tokens(expected: &str, actual: &str, epsilon: Option<(f64, f64)>) -> Verdict {
+    let expected_tokens: Vec<&str> = expected.split_whitespace().collect();
+    let actual_tokens: Vec<&str> = actual.split_whitespace().collect();
+
+    if expected_tokens.len() != actual_tokens.len() {
+        return Verdict::WrongAnswer(format!(
+            "token count mismatch: expected {} tokens, got {}",
+            expected_tokens.len(),
+            actual_tokens.len()
+        ));
+    }
+
+    for (index, (e, a)) in expected_tokens.iter().zip(actual_tokens.iter()).enumerate() {
+        let matches = match epsilon {
+            Some((abs, rel)) => match (e.parse::<f64>(), a.parse::<f64>()) {
+                (Ok(ev), Ok(av)) => {
+                    let diff = (ev - av).abs();
+                    diff <= abs || diff <= rel * ev.abs()
+                }
+                _ => e == a,
+            },
+            None => e == a,
+        };
+        if !matches {
+            return Verdict::WrongAnswer(format!(
+                "token {} diverged: expected '{}', got '{}'",
+                index + 1,
+                e,
+                a
+            ));
+        }
+    }
+
+    Verdict::Accepted
+}
+
+fn run_external_checker(
+    checker_path: &Path,
+    input_path: &Path,
+    expected_path: &Path,
+    actual: &str,
+) -> Result<Verdict> {
+    // Cases run concurrently (one OS thread per case in `runner::run_all`), so
+    // the scratch path must be unique per invocation, not just per process.
+    static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let unique_id = NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let actual_path = std::env::temp_dir().join(format!(
+        "xtask-checker-{}-{:?}-{unique_id}.actual",
+        std::process::id(),
+        std::thread::current().id(),
+    ));
+    fs::write(&actual_path, actual)
+        .with_context(|| format!("failed to write {}", actual_path.display()))?;
+
+    let output = Command::new(checker_path)
+        .arg(input_path)
+        .arg(expected_path)
+        .arg(&actual_path)
+        .output()
+        .with_context(|| format!("failed to run checker {}", checker_path.display()))?;
+
+    let _ = fs::remove_file(&actual_path);
+
+    if output.status.success() {
+        Ok(Verdict::Accepted)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Ok(Verdict::WrongAnswer(if stderr.is_empty() {
+            format!("checker exited with status {}", output.status)
+        } else {
+            stderr
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_tokens_ignores_whitespace_layout() {
+        assert_eq!(compare_tokens("1 2\n3\n", "1 2 3", None), Verdict::Accepted);
+    }
+
+    #[test]
+    fn compare_tokens_rejects_token_count_mismatch() {
+        assert!(matches!(
+            compare_tokens("1 2 3", "1 2", None),
+            Verdict::WrongAnswer(_)
+        ));
+    }
+
+    #[test]
+    fn compare_tokens_rejects_text_mismatch() {
+        assert!(matches!(
+            compare_tokens("foo bar", "foo baz", None),
+            Verdict::WrongAnswer(_)
+        ));
+    }
+
+    #[test]
+    fn compare_tokens_accepts_within_float_epsilon() {
+        assert_eq!(
+            compare_tokens("1.0 2.0", "1.00001 1.99999", Some((1e-3, 1e-3))),
+            Verdict::Accepted
+        );
+    }
+
+    #[test]
+    fn compare_tokens_rejects_outside_float_epsilon() {
+        assert!(matches!(
+            compare_tokens("1.0", "1.1", Some((1e-6, 1e-6))),
+            Verdict::WrongAnswer(_)
+        ));
+    }
+
+    #[test]
+    fn parse_defaults_to_exact() {
+        assert_eq!(
+            CheckerConfig::parse("", Path::new("tests/d")).unwrap(),
+            CheckerConfig::Exact
+        );
+    }
+
+    #[test]
+    fn parse_tokens_mode() {
+        assert_eq!(
+            CheckerConfig::parse("mode = \"tokens\"", Path::new("tests/d")).unwrap(),
+            CheckerConfig::Tokens
+        );
+    }
+
+    #[test]
+    fn parse_float_mode_with_defaults() {
+        assert_eq!(
+            CheckerConfig::parse("mode = \"float\"", Path::new("tests/d")).unwrap(),
+            CheckerConfig::Float { abs: 1e-6, rel: 1e-6 }
+        );
+    }
+
+    #[test]
+    fn parse_float_mode_with_explicit_tolerances() {
+        assert_eq!(
+            CheckerConfig::parse(
+                "mode = \"float\"\nabs = \"0.5\"\nrel = \"0.01\"",
+                Path::new("tests/d")
+            )
+            .unwrap(),
+            CheckerConfig::Float { abs: 0.5, rel: 0.01 }
+        );
+    }
+
+    #[test]
+    fn parse_strips_inline_comments() {
+        assert_eq!(
+            CheckerConfig::parse("mode = \"tokens\" # use token comparison", Path::new("tests/d"))
+                .unwrap(),
+            CheckerConfig::Tokens
+        );
+    }
+
+    #[test]
+    fn parse_checker_mode_resolves_path_against_task_dir() {
+        let parsed =
+            CheckerConfig::parse("mode = \"checker\"\npath = \"check.sh\"", Path::new("tests/d"))
+                .unwrap();
+        assert_eq!(
+            parsed,
+            CheckerConfig::Checker {
+                path: PathBuf::from("tests/d/check.sh")
+            }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_mode() {
+        assert!(CheckerConfig::parse("mode = \"bogus\"", Path::new("tests/d")).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Defaults and command aliases read from `xtask.toml` (or
+/// `.config/xtask.toml`), mirroring cargo's own config/alias lookup.
+///
+/// Precedence is always: explicit CLI flag > config value > hard-coded
+/// default. Callers merge these fields in on top of `clap`-parsed
+/// `Option`s rather than using them as `clap` defaults directly, so that an
+/// absent flag can be told apart from an explicit one.
+#[derive(Debug, Default, Clone)]
+pub struct XtaskConfig {
+    pub contest: Option<String>,
+    pub lang: Option<String>,
+    pub out_dir: Option<PathBuf>,
+    pub release: Option<bool>,
+    pub platform: Option<String>,
+    /// Path to a solution template file for `xtask new`, relative to the
+    /// project root. Falls back to the built-in template when unset.
+    pub template: Option<PathBuf>,
+    pub aliases: HashMap<String, String>,
+}
+
+impl XtaskConfig {
+    /// Load `xtask.toml` or `.config/xtask.toml` from `project_root`,
+    /// returning an all-`None` config when neither file exists.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        for candidate in ["xtask.toml", ".config/xtask.toml"] {
+            let path = project_root.join(candidate);
+            if path.exists() {
+                let contents = fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                return Self::parse(&contents);
+            }
+        }
+        Ok(Self::default())
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let mut config = Self::default();
+        let mut section: Option<String> = None;
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                section = Some(name.trim().to_string());
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = strip_inline_comment(value.trim()).trim_matches('"').to_string();
+
+            match section.as_deref() {
+                Some("alias") => {
+                    config.aliases.insert(key.to_string(), value);
+                }
+                None => match key {
+                    "contest" => config.contest = Some(value),
+                    "lang" => config.lang = Some(value),
+                    "out_dir" | "tests_dir" => config.out_dir = Some(PathBuf::from(value)),
+                    "release" => {
+                        config.release = Some(
+                            value
+                                .parse::<bool>()
+                                .with_context(|| format!("invalid 'release' value '{value}'"))?,
+                        )
+                    }
+                    "platform" => config.platform = Some(value),
+                    "template" => config.template = Some(PathBuf::from(value)),
+                    _ => {}
+                },
+                Some(other) => anyhow::bail!("unknown xtask.toml section '[{other}]'"),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Resolve a cargo-style alias: if `args[1]` names an alias, splice its
+    /// whitespace-separated expansion in its place. Leaves `args` untouched
+    /// when there is no match, including when `args` is too short to have a
+    /// subcommand at all.
+    pub fn resolve_alias(&self, args: Vec<String>) -> Vec<String> {
+        let Some(command) = args.get(1) else {
+            return args;
+        };
+        let Some(expansion) = self.aliases.get(command) else {
+            return args;
+        };
+
+        let mut resolved = vec![args[0].clone()];
+        resolved.extend(expansion.split_whitespace().map(str::to_string));
+        resolved.extend(args.into_iter().skip(2));
+        resolved
+    }
+}
+
+/// Strip a trailing `# comment` from a config value, ignoring any `#` that
+/// appears inside a `"..."` quoted string so quoted values can contain one.
+fn strip_inline_comment(value: &str) -> &str {
+    let mut in_quotes = false;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '#' if !in_quotes => return value[..index].trim_end(),
+            _ => {}
+        }
+    }
+    value
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use regex::Regex;
+use scraper::{Html, Selector};
+
+/// A single input/output pair for one sample case.
+#[derive(Debug, Clone)]
+pub struct SamplePair {
+    pub input: String,
+    pub output: String,
+}
+
+/// An online judge backend: knows how to build a problem URL and how to
+/// scrape sample cases out of that problem's HTML page.
+///
+/// Adding a new judge means implementing this trait and wiring it up in
+/// [`judge_for`]; nothing else in `xtask` needs to change.
+pub trait Judge {
+    /// The problem statement URL for `task` (or `problem_id`, when given)
+    /// within `contest`, optionally localized via `lang`.
+    fn problem_url(&self, contest: &str, task: &str, problem_id: &str, lang: Option<&str>)
+        -> String;
+
+    /// Scrape sample input/output pairs out of the problem page, keyed by
+    /// 1-based sample index.
+    fn parse_samples(&self, html: &str) -> Result<BTreeMap<usize, SamplePair>>;
+}
+
+/// Look up the `Judge` implementation for a `--platform` name.
+pub fn judge_for(platform: &str) -> Result<Box<dyn Judge>> {
+    match platform.to_ascii_lowercase().as_str() {
+        "atcoder" => Ok(Box::new(AtCoder)),
+        "codeforces" => Ok(Box::new(Codeforces)),
+        other => anyhow::bail!("unknown platform '{other}' (expected atcoder or codeforces)"),
+    }
+}
+
+/// AtCoder: `<section><h3>Sample Input/Output N</h3><pre>...</pre></section>`.
+pub struct AtCoder;
+
+impl Judge for AtCoder {
+    fn problem_url(
+        &self,
+        contest: &str,
+        _task: &str,
+        problem_id: &str,
+        lang: Option<&str>,
+    ) -> String {
+        let mut url = format!("https://atcoder.jp/contests/{contest}/tasks/{problem_id}");
+        if let Some(lang) = lang {
+            if !lang.is_empty() {
+                url.push_str(&format!("?lang={lang}"));
+            }
+        }
+        url
+    }
+
+    fn parse_samples(&self, html: &str) -> Result<BTreeMap<usize, SamplePair>> {
+        let document = Html::parse_document(html);
+        let section_selector = Selector::parse("section").unwrap();
+        let heading_selector = Selector::parse("h3").unwrap();
+        let pre_selector = Selector::parse("pre").unwrap();
+
+        let index_regex = Regex::new(r"(\d+)(?:\s*)$").unwrap();
+
+        let mut inputs: BTreeMap<usize, String> = BTreeMap::new();
+        let mut outputs: BTreeMap<usize, String> = BTreeMap::new();
+
+        for section in document.select(&section_selector) {
+            let Some(heading) = section.select(&heading_selector).next() else {
+                continue;
+            };
+            let title = heading.text().collect::<String>().trim().to_string();
+            let Some(kind) = classify_heading(&title) else {
+                continue;
+            };
+            let Some(pre) = section.select(&pre_selector).next() else {
+                continue;
+            };
+            let content = normalize_pre(&pre.text().collect::<String>());
+            let Some(captures) = index_regex.captures(&title) else {
+                continue;
+            };
+            let index: usize = captures[1].parse().unwrap_or(0);
+            if index == 0 {
+                continue;
+            }
+            match kind {
+                SampleKind::Input => {
+                    inputs.insert(index, ensure_trailing_newline(content));
+                }
+                SampleKind::Output => {
+                    outputs.insert(index, ensure_trailing_newline(content));
+                }
+            }
+        }
+
+        Ok(zip_samples(inputs, outputs))
+    }
+}
+
+fn classify_heading(title: &str) -> Option<SampleKind> {
+    let title = title.trim();
+    if title.contains("Sample Input") || title.contains("入力例") {
+        Some(SampleKind::Input)
+    } else if title.contains("Sample Output") || title.contains("出力例") {
+        Some(SampleKind::Output)
+    } else {
+        None
+    }
+}
+
+enum SampleKind {
+    Input,
+    Output,
+}
+
+/// Codeforces: `<div class="sample-test"><div class="input"><pre>...</pre></div>
+/// <div class="output"><pre>...</pre></div></div>`, one pair per problem
+/// statement in document order (no explicit index in the markup).
+pub struct Codeforces;
+
+impl Judge for Codeforces {
+    fn problem_url(
+        &self,
+        contest: &str,
+        task: &str,
+        _problem_id: &str,
+        _lang: Option<&str>,
+    ) -> String {
+        format!("https://codeforces.com/contest/{contest}/problem/{task}")
+    }
+
+    fn parse_samples(&self, html: &str) -> Result<BTreeMap<usize, SamplePair>> {
+        let document = Html::parse_document(html);
+        let input_selector = Selector::parse("div.input > pre").unwrap();
+        let output_selector = Selector::parse("div.output > pre").unwrap();
+
+        let mut inputs: BTreeMap<usize, String> = BTreeMap::new();
+        for (index, pre) in document.select(&input_selector).enumerate() {
+            let content = normalize_pre(&pre_text(&pre));
+            inputs.insert(index + 1, ensure_trailing_newline(content));
+        }
+
+        let mut outputs: BTreeMap<usize, String> = BTreeMap::new();
+        for (index, pre) in document.select(&output_selector).enumerate() {
+            let content = normalize_pre(&pre_text(&pre));
+            outputs.insert(index + 1, ensure_trailing_newline(content));
+        }
+
+        Ok(zip_samples(inputs, outputs))
+    }
+}
+
+/// Codeforces renders each line of a sample as its own child `<div>` rather
+/// than raw text, so join them with newlines instead of concatenating.
+fn pre_text(pre: &scraper::ElementRef) -> String {
+    let lines: Vec<String> = pre.text().map(|t| t.to_string()).collect();
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.join("\n")
+    }
+}
+
+fn zip_samples(
+    inputs: BTreeMap<usize, String>,
+    mut outputs: BTreeMap<usize, String>,
+) -> BTreeMap<usize, SamplePair> {
+    let mut samples = BTreeMap::new();
+    for (index, input) in inputs {
+        if let Some(output) = outputs.remove(&index) {
+            samples.insert(index, SamplePair { input, output });
+        }
+    }
+    samples
+}
+
+fn normalize_pre(raw: &str) -> String {
+    raw.replace("\r\n", "\n")
+}
+
+fn ensure_trailing_newline(mut text: String) -> String {
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
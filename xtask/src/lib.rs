@@ -0,0 +1,5 @@
+pub mod checker;
+pub mod config;
+pub mod judge;
+pub mod runner;
+pub mod session;
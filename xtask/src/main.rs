@@ -1,4 +1,3 @@
-use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::io::Write;
@@ -7,11 +6,17 @@ use std::process::{Command, Stdio};
 
 use anyhow::{anyhow, bail, Context, Result};
 use clap::{Args, Parser, Subcommand};
-use regex::Regex;
 use scraper::{Html, Selector};
 
+use xtask::config::XtaskConfig;
+use xtask::judge::judge_for;
+use xtask::runner;
+use xtask::session::Session;
+
 fn main() -> Result<()> {
-    Cli::parse().run()
+    let config = XtaskConfig::load(&project_root())?;
+    let args = config.resolve_alias(env::args().collect());
+    Cli::parse_from(args).run(&config)
 }
 
 #[derive(Parser)]
@@ -27,11 +32,13 @@ struct Cli {
 }
 
 impl Cli {
-    fn run(self) -> Result<()> {
+    fn run(self, config: &XtaskConfig) -> Result<()> {
         match self.command {
-            CommandKind::Fetch(args) => fetch_samples(args),
-            CommandKind::Run(args) => run_case(args),
-            CommandKind::Test(args) => run_tests(args),
+            CommandKind::Fetch(args) => fetch_samples(args, config),
+            CommandKind::Run(args) => run_case(args, config),
+            CommandKind::Test(args) => run_tests(args, config),
+            CommandKind::New(args) => new_task(args, config),
+            CommandKind::Login(args) => login(args),
         }
     }
 }
@@ -44,6 +51,10 @@ enum CommandKind {
     Run(RunArgs),
     /// Run tests (optionally scoped to a single task)
     Test(TestArgs),
+    /// Scaffold a new task: src/bin/<task>.rs and tests/<task>_test.rs
+    New(NewArgs),
+    /// Log in to AtCoder and persist the session for --platform atcoder fetches
+    Login(LoginArgs),
 }
 
 #[derive(Args)]
@@ -57,12 +68,16 @@ struct FetchArgs {
     /// Language query parameter (ja/en)
     #[arg(long)]
     lang: Option<String>,
-    /// Output root directory relative to the project (default: tests)
-    #[arg(long, default_value = "tests")]
-    out_dir: PathBuf,
+    /// Output root directory relative to the project (default: tests, or
+    /// xtask.toml's `out_dir`)
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
     /// Overwrite existing sample files
     #[arg(long)]
     overwrite: bool,
+    /// Online judge to fetch from (default: atcoder, or xtask.toml's `platform`)
+    #[arg(long)]
+    platform: Option<String>,
 }
 
 #[derive(Args)]
@@ -71,56 +86,109 @@ struct RunArgs {
     bin: String,
     /// Sample id (e.g. 1 or 001). Defaults to 001 when omitted.
     case: Option<String>,
-    /// Root directory for tests (default: tests)
-    #[arg(long, default_value = "tests")]
-    tests_dir: PathBuf,
-    /// Run in release mode
+    /// Root directory for tests (default: tests, or xtask.toml's `out_dir`)
     #[arg(long)]
+    tests_dir: Option<PathBuf>,
+    /// Run in release mode (overrides xtask.toml's `release`)
+    #[arg(long, overrides_with = "no_release")]
     release: bool,
+    /// Run in debug mode even if xtask.toml sets `release = true`
+    #[arg(long)]
+    no_release: bool,
+    /// Run every sample case concurrently instead of just one, reporting
+    /// per-case verdicts (AC/WA/RE/TLE) and timings
+    #[arg(long)]
+    all: bool,
+    /// Per-case time limit in milliseconds, used with --all (default: 2000)
+    #[arg(long, default_value_t = 2000)]
+    time_limit: u64,
 }
 
 #[derive(Args)]
 struct TestArgs {
     /// Optional task letter; when omitted runs the entire suite
     target: Option<String>,
-    /// Test in release mode
-    #[arg(long)]
+    /// Test in release mode (overrides xtask.toml's `release`)
+    #[arg(long, overrides_with = "no_release")]
     release: bool,
+    /// Test in debug mode even if xtask.toml sets `release = true`
+    #[arg(long)]
+    no_release: bool,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Task letter (e.g. e)
+    task: String,
+    /// Immediately fetch samples into tests/<task>/ after scaffolding
+    #[arg(long)]
+    fetch: bool,
+    /// Contest id used when --fetch is set (defaults like `fetch`'s own default)
+    #[arg(long)]
+    contest: Option<String>,
+    /// Language query parameter forwarded to --fetch
+    #[arg(long)]
+    lang: Option<String>,
+    /// Platform forwarded to --fetch
+    #[arg(long)]
+    platform: Option<String>,
+    /// Solution template file to scaffold from, relative to the project
+    /// root (default: xtask.toml's `template`, or a built-in template)
+    #[arg(long)]
+    template: Option<PathBuf>,
 }
 
-fn fetch_samples(args: FetchArgs) -> Result<()> {
+#[derive(Args)]
+struct LoginArgs {
+    /// AtCoder username
+    #[arg(long)]
+    username: String,
+}
+
+fn fetch_samples(args: FetchArgs, config: &XtaskConfig) -> Result<()> {
     let FetchArgs {
         identifiers,
         problem_id,
         lang,
         out_dir,
         overwrite,
+        platform,
     } = args;
 
     let (contest, task) = match identifiers.as_slice() {
-        [task] => (default_contest(), task.clone()),
+        [task] => (config.contest.clone().unwrap_or_else(default_contest), task.clone()),
         [contest, task] => (contest.clone(), task.clone()),
         _ => bail!("expected 1 or 2 identifiers, got {}", identifiers.len()),
     };
 
+    let lang = lang.or_else(|| config.lang.clone());
+    let out_dir = out_dir
+        .or_else(|| config.out_dir.clone())
+        .unwrap_or_else(|| PathBuf::from("tests"));
+    let platform = platform
+        .or_else(|| config.platform.clone())
+        .unwrap_or_else(|| "atcoder".to_string());
+
+    let judge = judge_for(&platform)?;
     let problem_id = problem_id.unwrap_or_else(|| format!("{}_{}", contest, task));
-    let mut url = format!(
-        "https://atcoder.jp/contests/{}/tasks/{}",
-        contest, problem_id
-    );
-    if let Some(lang) = lang.as_deref() {
-        if !lang.is_empty() {
-            url.push_str(&format!("?lang={lang}"));
-        }
-    }
+    let url = judge.problem_url(&contest, &task, &problem_id, lang.as_deref());
+
+    let project_root = project_root();
+    let session = Session::load(&project_root)?;
 
     let client = reqwest::blocking::Client::builder()
         .user_agent("atcoder-rust-template xtask")
         .build()
         .context("failed to build HTTP client")?;
 
-    let body = client
-        .get(&url)
+    let mut request = client.get(&url);
+    if let Some(session) = &session {
+        if !session.is_empty() {
+            request = request.header(reqwest::header::COOKIE, session.header_value());
+        }
+    }
+
+    let body = request
         .send()
         .with_context(|| format!("failed to download {url}"))?
         .error_for_status()
@@ -128,12 +196,22 @@ fn fetch_samples(args: FetchArgs) -> Result<()> {
         .text()
         .context("failed to read response body")?;
 
-    let samples = parse_samples(&body)?;
+    let samples = judge.parse_samples(&body)?;
     if samples.is_empty() {
+        if platform == "atcoder" && session.as_ref().is_none_or(Session::is_empty) {
+            bail!(
+                "no samples found on the page; this problem may require a logged-in session \
+                 during the contest — run `xtask login` and try again"
+            );
+        }
+        if platform == "atcoder" {
+            bail!(
+                "no samples found on the page; your saved session may have expired — \
+                 run `xtask login` again"
+            );
+        }
         bail!("no samples found on the page");
     }
-
-    let project_root = project_root();
     let task_dir = task.to_ascii_lowercase();
     let out_dir = project_root.join(out_dir).join(task_dir);
     fs::create_dir_all(&out_dir)
@@ -172,6 +250,71 @@ fn fetch_samples(args: FetchArgs) -> Result<()> {
     Ok(())
 }
 
+fn login(args: LoginArgs) -> Result<()> {
+    const LOGIN_URL: &str = "https://atcoder.jp/login";
+
+    let password = rpassword::prompt_password("AtCoder password: ")
+        .context("failed to read password")?;
+
+    let project_root = project_root();
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("atcoder-rust-template xtask")
+        .cookie_store(true)
+        .build()
+        .context("failed to build HTTP client")?;
+
+    let get_response = client
+        .get(LOGIN_URL)
+        .send()
+        .with_context(|| format!("failed to download {LOGIN_URL}"))?
+        .error_for_status()
+        .with_context(|| format!("server returned error for {LOGIN_URL}"))?;
+
+    let mut session = Session::default();
+    session.update_from_response(&get_response);
+    let body = get_response.text().context("failed to read login page")?;
+    let csrf_token = extract_csrf_token(&body)?;
+
+    let post_response = client
+        .post(LOGIN_URL)
+        .header(reqwest::header::COOKIE, session.header_value())
+        .form(&[
+            ("username", args.username.as_str()),
+            ("password", password.as_str()),
+            ("csrf_token", csrf_token.as_str()),
+        ])
+        .send()
+        .with_context(|| format!("failed to POST {LOGIN_URL}"))?
+        .error_for_status()
+        .with_context(|| format!("server returned error for {LOGIN_URL}"))?;
+
+    session.update_from_response(&post_response);
+    let body = post_response
+        .text()
+        .context("failed to read login response")?;
+    if body.contains(r#"name="username""#) {
+        bail!("login failed: check your username and password");
+    }
+
+    session.save(&project_root)?;
+    println!(
+        "saved session to {}",
+        rel_path(&Session::path(&project_root))?
+    );
+    Ok(())
+}
+
+fn extract_csrf_token(html: &str) -> Result<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(r#"input[name="csrf_token"]"#).unwrap();
+    document
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr("value"))
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("csrf_token input not found on login page"))
+}
+
 fn default_contest() -> String {
     env::current_dir()
         .ok()
@@ -181,13 +324,38 @@ fn default_contest() -> String {
         .unwrap_or_else(|| "contest".to_string())
 }
 
-fn run_case(args: RunArgs) -> Result<()> {
+/// Reconcile the `--release`/`--no-release` flag pair into a tri-state
+/// value: `Some(_)` only when one was explicitly passed, so it can be
+/// layered on top of `config.release` with the usual CLI-beats-config
+/// precedence instead of a plain `bool` silently losing the "absent" case.
+fn explicit_release(release: bool, no_release: bool) -> Option<bool> {
+    if release {
+        Some(true)
+    } else if no_release {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn run_case(args: RunArgs, config: &XtaskConfig) -> Result<()> {
     let project_root = project_root();
     let bin = args.bin;
-    let case = args.case.unwrap_or_else(|| "001".to_string());
-    let tests_dir = project_root.join(args.tests_dir);
+    let release = explicit_release(args.release, args.no_release)
+        .or(config.release)
+        .unwrap_or(false);
+    let tests_dir = args
+        .tests_dir
+        .or_else(|| config.out_dir.clone())
+        .unwrap_or_else(|| PathBuf::from("tests"));
+    let tests_dir = project_root.join(tests_dir);
     let dir = tests_dir.join(&bin);
 
+    if args.all {
+        return run_all_cases_cli(&project_root, &bin, &dir, release, args.time_limit);
+    }
+
+    let case = args.case.unwrap_or_else(|| "001".to_string());
     let candidates = candidate_inputs(&dir, &case);
     let input_path = candidates
         .into_iter()
@@ -200,7 +368,7 @@ fn run_case(args: RunArgs) -> Result<()> {
     println!("cargo run --bin {bin} < {}", rel_path(&input_path)?);
     let mut command = Command::new("cargo");
     command.arg("run").arg("--bin").arg(&bin);
-    if args.release {
+    if release {
         command.arg("--release");
     }
     command.current_dir(&project_root);
@@ -223,9 +391,77 @@ fn run_case(args: RunArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_tests(args: TestArgs) -> Result<()> {
+fn run_all_cases_cli(
+    project_root: &Path,
+    bin: &str,
+    dir: &Path,
+    release: bool,
+    time_limit_ms: u64,
+) -> Result<()> {
+    let mut build = Command::new("cargo");
+    build.arg("build").arg("--bin").arg(bin);
+    if release {
+        build.arg("--release");
+    }
+    build.current_dir(project_root);
+    let status = build.status().context("failed to spawn cargo build")?;
+    if !status.success() {
+        bail!("cargo build exited with status {status}");
+    }
+
+    let profile_dir = if release { "release" } else { "debug" };
+    let bin_path = project_root.join("target").join(profile_dir).join(bin);
+
+    let results = runner::run_all(
+        &bin_path,
+        dir,
+        std::time::Duration::from_millis(time_limit_ms),
+    )?;
+    if results.is_empty() {
+        println!("no sample cases found in {}", rel_path(dir)?);
+        return Ok(());
+    }
+
+    let slowest = results
+        .iter()
+        .max_by_key(|r| r.elapsed)
+        .map(|r| r.case.clone());
+
+    for result in &results {
+        let marker = if Some(&result.case) == slowest.as_ref() {
+            " (slowest)"
+        } else {
+            ""
+        };
+        println!(
+            "{:<8} {:<4} {:>7.1}ms{}",
+            result.case,
+            result.verdict.label(),
+            result.elapsed.as_secs_f64() * 1000.0,
+            marker
+        );
+        match &result.verdict {
+            runner::CaseVerdict::WrongAnswer(reason) => {
+                println!("  {reason}");
+                runner::print_diff(&result.expected, &result.actual);
+            }
+            runner::CaseVerdict::RuntimeError(reason) => {
+                println!("  {reason}");
+            }
+            _ => {}
+        }
+    }
+
+    println!("{}", runner::summarize(&results));
+    Ok(())
+}
+
+fn run_tests(args: TestArgs, config: &XtaskConfig) -> Result<()> {
     let project_root = project_root();
     let package = package_name()?;
+    let release = explicit_release(args.release, args.no_release)
+        .or(config.release)
+        .unwrap_or(false);
     let mut command = Command::new("cargo");
     command.arg("test");
     command.arg("-p").arg(&package);
@@ -246,7 +482,7 @@ fn run_tests(args: TestArgs) -> Result<()> {
         filter_args.push(format!("{task_slug}_all_cases"));
     }
 
-    if args.release {
+    if release {
         command.arg("--release");
     }
     if !filter_args.is_empty() {
@@ -267,87 +503,106 @@ fn run_tests(args: TestArgs) -> Result<()> {
     Ok(())
 }
 
-fn candidate_inputs(dir: &Path, case: &str) -> Vec<PathBuf> {
-    let mut paths = Vec::new();
-    paths.push(dir.join(format!("{case}.in")));
-    if case.chars().all(|c| c.is_ascii_digit()) {
-        if let Ok(value) = case.parse::<usize>() {
-            paths.push(dir.join(format!("{value:03}.in")));
-        }
-    }
-    paths
-}
-
-fn parse_samples(html: &str) -> Result<BTreeMap<usize, SamplePair>> {
-    let document = Html::parse_document(html);
-    let section_selector = Selector::parse("section").unwrap();
-    let heading_selector = Selector::parse("h3").unwrap();
-    let pre_selector = Selector::parse("pre").unwrap();
-
-    let index_regex = Regex::new(r"(\d+)(?:\s*)$").unwrap();
+fn new_task(args: NewArgs, config: &XtaskConfig) -> Result<()> {
+    let task = args.task.to_ascii_lowercase();
+    let project_root = project_root();
 
-    let mut inputs: BTreeMap<usize, String> = BTreeMap::new();
-    let mut outputs: BTreeMap<usize, String> = BTreeMap::new();
+    // Resolve the same effective out_dir that `fetch_samples` would use, so
+    // the scaffolded test looks in the directory `--fetch` actually writes to.
+    let out_dir = config.out_dir.clone().unwrap_or_else(|| PathBuf::from("tests"));
+    let samples_dir = out_dir.join(&task);
 
-    for section in document.select(&section_selector) {
-        let Some(heading) = section.select(&heading_selector).next() else {
-            continue;
-        };
-        let title = heading.text().collect::<String>().trim().to_string();
-        let Some(kind) = classify_heading(&title) else {
-            continue;
-        };
-        let Some(pre) = section.select(&pre_selector).next() else {
-            continue;
-        };
-        let content = normalize_pre(&pre.text().collect::<String>());
-        let Some(captures) = index_regex.captures(&title) else {
-            continue;
-        };
-        let index: usize = captures[1].parse().unwrap_or(0);
-        if index == 0 {
-            continue;
-        }
-        match kind {
-            SampleKind::Input => {
-                inputs.insert(index, ensure_trailing_newline(content));
-            }
-            SampleKind::Output => {
-                outputs.insert(index, ensure_trailing_newline(content));
-            }
-        }
+    let bin_path = project_root.join("src/bin").join(format!("{task}.rs"));
+    let test_path = project_root.join("tests").join(format!("{task}_test.rs"));
+    if bin_path.exists() {
+        bail!("{} already exists", rel_path(&bin_path)?);
+    }
+    if test_path.exists() {
+        bail!("{} already exists", rel_path(&test_path)?);
     }
 
-    let mut samples = BTreeMap::new();
-    for (index, input) in inputs {
-        if let Some(output) = outputs.get(&index).cloned() {
-            samples.insert(index, SamplePair { input, output });
-        }
+    let template = args
+        .template
+        .clone()
+        .or_else(|| config.template.clone())
+        .map(|path| {
+            let path = project_root.join(&path);
+            fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))
+        })
+        .transpose()?
+        .unwrap_or_else(|| solution_template().to_string());
+
+    fs::create_dir_all(bin_path.parent().unwrap())
+        .with_context(|| format!("failed to create {}", bin_path.parent().unwrap().display()))?;
+    fs::write(&bin_path, &template)
+        .with_context(|| format!("failed to write {}", bin_path.display()))?;
+    println!("wrote {}", rel_path(&bin_path)?);
+
+    fs::write(&test_path, test_template(&task, &samples_dir))
+        .with_context(|| format!("failed to write {}", test_path.display()))?;
+    println!("wrote {}", rel_path(&test_path)?);
+
+    if args.fetch {
+        let identifiers = match args.contest {
+            Some(contest) => vec![contest, task.clone()],
+            None => vec![task.clone()],
+        };
+        fetch_samples(
+            FetchArgs {
+                identifiers,
+                problem_id: None,
+                lang: args.lang,
+                out_dir: Some(out_dir),
+                overwrite: false,
+                platform: args.platform,
+            },
+            config,
+        )?;
     }
 
-    Ok(samples)
+    Ok(())
 }
 
-fn classify_heading(title: &str) -> Option<SampleKind> {
-    let title = title.trim();
-    if title.contains("Sample Input") || title.contains("入力例") {
-        Some(SampleKind::Input)
-    } else if title.contains("Sample Output") || title.contains("出力例") {
-        Some(SampleKind::Output)
-    } else {
-        None
-    }
+/// The built-in solution template, used when neither `--template` nor
+/// xtask.toml's `template` points at a file.
+fn solution_template() -> &'static str {
+    r#"use std::io::{self, Read};
+
+fn main() {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input).unwrap();
+    let mut iter = input.split_whitespace();
+    let _ = &mut iter; // TODO: parse input
+
+    // TODO: solve
+}
+"#
 }
 
-fn normalize_pre(raw: &str) -> String {
-    raw.replace("\r\n", "\n")
+fn test_template(task: &str, samples_dir: &Path) -> String {
+    let samples_dir = samples_dir.display();
+    format!(
+        r#"mod common;
+
+use common::run_all_cases;
+
+#[test]
+fn {task}_all_cases() {{
+    run_all_cases("{samples_dir}", "{task}");
+}}
+"#
+    )
 }
 
-fn ensure_trailing_newline(mut text: String) -> String {
-    if !text.ends_with('\n') {
-        text.push('\n');
+fn candidate_inputs(dir: &Path, case: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    paths.push(dir.join(format!("{case}.in")));
+    if case.chars().all(|c| c.is_ascii_digit()) {
+        if let Ok(value) = case.parse::<usize>() {
+            paths.push(dir.join(format!("{value:03}.in")));
+        }
     }
-    text
+    paths
 }
 
 fn project_root() -> PathBuf {
@@ -396,15 +651,3 @@ fn package_name() -> Result<String> {
 
     bail!("package name not found in Cargo.toml");
 }
-
-#[derive(Debug, Clone)]
-struct SamplePair {
-    input: String,
-    output: String,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum SampleKind {
-    Input,
-    Output,
-}
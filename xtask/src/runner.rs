@@ -0,0 +1,223 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use crate::checker::{CheckerConfig, Verdict as CheckVerdict};
+
+/// Outcome of running one sample case against a built binary.
+#[derive(Debug, Clone)]
+pub enum CaseVerdict {
+    Accepted,
+    WrongAnswer(String),
+    RuntimeError(String),
+    TimeLimitExceeded,
+}
+
+impl CaseVerdict {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CaseVerdict::Accepted => "AC",
+            CaseVerdict::WrongAnswer(_) => "WA",
+            CaseVerdict::RuntimeError(_) => "RE",
+            CaseVerdict::TimeLimitExceeded => "TLE",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    pub case: String,
+    pub verdict: CaseVerdict,
+    pub elapsed: Duration,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Run every `NNN.in`/`NNN.out` pair under `dir` against `bin_path`
+/// concurrently (one OS thread per case), honoring `time_limit` and the
+/// directory's [`CheckerConfig`].
+pub fn run_all(bin_path: &Path, dir: &Path, time_limit: Duration) -> Result<Vec<CaseResult>> {
+    let checker = CheckerConfig::load(dir)?;
+
+    let mut cases: Vec<(String, PathBuf, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("in") {
+            let stem = path.file_stem().unwrap().to_string_lossy().to_string();
+            let expected_path = dir.join(format!("{stem}.out"));
+            if expected_path.exists() {
+                cases.push((stem, path, expected_path));
+            }
+        }
+    }
+    cases.sort();
+
+    let (tx, rx) = mpsc::channel();
+    for (stem, input_path, expected_path) in cases {
+        let tx = tx.clone();
+        let bin_path = bin_path.to_path_buf();
+        let checker = checker.clone();
+        thread::spawn(move || {
+            let result = run_one_case(&bin_path, &stem, &input_path, &expected_path, &checker, time_limit);
+            let _ = tx.send(result);
+        });
+    }
+    drop(tx);
+
+    let mut results = rx.into_iter().collect::<Result<Vec<_>>>()?;
+    results.sort_by(|a, b| a.case.cmp(&b.case));
+    Ok(results)
+}
+
+fn run_one_case(
+    bin_path: &Path,
+    stem: &str,
+    input_path: &Path,
+    expected_path: &Path,
+    checker: &CheckerConfig,
+    time_limit: Duration,
+) -> Result<CaseResult> {
+    let input = fs::read(input_path)
+        .with_context(|| format!("failed to read {}", input_path.display()))?;
+    let expected = fs::read_to_string(expected_path)
+        .with_context(|| format!("failed to read {}", expected_path.display()))?;
+
+    let mut child = Command::new(bin_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {}", bin_path.display()))?;
+
+    // Start the watchdog clock as soon as the child exists, and write stdin
+    // and drain stdout/stderr all concurrently with it: a solution that
+    // interleaves reading stdin with writing substantial output (or simply
+    // writes more than the OS pipe buffer, routine for competitive
+    // programming) would otherwise deadlock before the watchdog ever got a
+    // chance to observe it, since nothing would read/write the pipes until
+    // the child was already observed to exit.
+    let start = Instant::now();
+
+    let mut stdin = child.stdin.take().context("failed to open child stdin")?;
+    let stdin_writer = thread::spawn(move || {
+        let _ = stdin.write_all(&input);
+    });
+
+    let mut stdout = child.stdout.take().context("failed to open child stdout")?;
+    let mut stderr = child.stderr.take().context("failed to open child stderr")?;
+    let stdout_reader = thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stdout.read_to_string(&mut buf);
+        buf
+    });
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let status = loop {
+        if let Some(status) = child.try_wait().context("failed to poll child process")? {
+            break Some(status);
+        }
+        if start.elapsed() >= time_limit {
+            break None;
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+
+    match status {
+        Some(status) => {
+            let elapsed = start.elapsed();
+            let _ = stdin_writer.join();
+            let actual = stdout_reader.join().unwrap_or_default();
+            let stderr_output = stderr_reader.join().unwrap_or_default();
+
+            let verdict = if !status.success() {
+                CaseVerdict::RuntimeError(if stderr_output.trim().is_empty() {
+                    format!("exited with status {status}")
+                } else {
+                    stderr_output.trim().to_string()
+                })
+            } else {
+                match checker.check(input_path, expected_path, &expected, &actual)? {
+                    CheckVerdict::Accepted => CaseVerdict::Accepted,
+                    CheckVerdict::WrongAnswer(reason) => CaseVerdict::WrongAnswer(reason),
+                }
+            };
+
+            Ok(CaseResult {
+                case: stem.to_string(),
+                verdict,
+                elapsed,
+                expected,
+                actual,
+            })
+        }
+        None => {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = stdin_writer.join();
+            let _ = stdout_reader.join();
+            let _ = stderr_reader.join();
+            Ok(CaseResult {
+                case: stem.to_string(),
+                verdict: CaseVerdict::TimeLimitExceeded,
+                elapsed: time_limit,
+                expected,
+                actual: String::new(),
+            })
+        }
+    }
+}
+
+/// Print a unified, color-highlighted line-by-line diff of `expected` vs `actual`.
+pub fn print_diff(expected: &str, actual: &str) {
+    const RED: &str = "\x1b[31m";
+    const GREEN: &str = "\x1b[32m";
+    const RESET: &str = "\x1b[0m";
+
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let line_count = expected_lines.len().max(actual_lines.len());
+
+    for i in 0..line_count {
+        let expected_line = expected_lines.get(i).copied();
+        let actual_line = actual_lines.get(i).copied();
+        match (expected_line, actual_line) {
+            (Some(e), Some(a)) if e == a => println!("  {e}"),
+            (e, a) => {
+                if let Some(e) = e {
+                    println!("{RED}- {e}{RESET}");
+                }
+                if let Some(a) = a {
+                    println!("{GREEN}+ {a}{RESET}");
+                }
+            }
+        }
+    }
+}
+
+/// Summarize a set of case results as e.g. `3/5 AC, 1 WA, 1 TLE`.
+pub fn summarize(results: &[CaseResult]) -> String {
+    let total = results.len();
+    let accepted = results
+        .iter()
+        .filter(|r| matches!(r.verdict, CaseVerdict::Accepted))
+        .count();
+
+    let mut parts = vec![format!("{accepted}/{total} AC")];
+    for label in ["WA", "RE", "TLE"] {
+        let count = results.iter().filter(|r| r.verdict.label() == label).count();
+        if count > 0 {
+            parts.push(format!("{count} {label}"));
+        }
+    }
+    parts.join(", ")
+}
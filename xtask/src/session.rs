@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Response;
+use reqwest::header::SET_COOKIE;
+
+/// A persisted AtCoder cookie jar, stored under `.xtask/session.json` so
+/// `fetch` can reuse it across invocations without logging in every time.
+#[derive(Debug, Default, Clone)]
+pub struct Session {
+    cookies: BTreeMap<String, String>,
+}
+
+impl Session {
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".xtask").join("session.json")
+    }
+
+    /// Load a previously saved session, if one exists.
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(project_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(Some(Session {
+            cookies: parse_json_object(&contents),
+        }))
+    }
+
+    /// Persist this session's cookies to `.xtask/session.json`, restricting
+    /// the file to owner-only access since it's equivalent to full account
+    /// access.
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let path = Self::path(project_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, to_json_object(&self.cookies))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        restrict_permissions(&path)
+            .with_context(|| format!("failed to restrict permissions on {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Absorb any `Set-Cookie` headers from a response into this session.
+    pub fn update_from_response(&mut self, response: &Response) {
+        for header in response.headers().get_all(SET_COOKIE).iter() {
+            let Ok(header) = header.to_str() else {
+                continue;
+            };
+            let Some(pair) = header.split(';').next() else {
+                continue;
+            };
+            if let Some((name, value)) = pair.split_once('=') {
+                self.cookies.insert(name.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+
+    /// Render the cookies as a `Cookie:` header value.
+    pub fn header_value(&self) -> String {
+        self.cookies
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+}
+
+fn to_json_object(map: &BTreeMap<String, String>) -> String {
+    let mut out = String::from("{\n");
+    for (index, (key, value)) in map.iter().enumerate() {
+        out.push_str(&format!(
+            "  \"{}\": \"{}\"",
+            escape_json(key),
+            escape_json(value)
+        ));
+        if index + 1 < map.len() {
+            out.push(',');
+        }
+        out.push('\n');
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn parse_json_object(contents: &str) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+    for line in contents.lines() {
+        let line = line.trim().trim_end_matches(',');
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+        let value = value.trim().trim_matches('"');
+        if key.is_empty() {
+            continue;
+        }
+        map.insert(unescape_json(key), unescape_json(value));
+    }
+    map
+}
+
+fn escape_json(raw: &str) -> String {
+    raw.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape_json(raw: &str) -> String {
+    raw.replace("\\\"", "\"").replace("\\\\", "\\")
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}